@@ -1,4 +1,4 @@
-use crate::platform::{DisplayResolution, PlatformScreenCapture};
+use crate::platform::{DisplayInfo, DisplayResolution, PlatformScreenCapture};
 
 #[cfg(target_os = "macos")]
 use crate::platform::MacosScreenCapture as Backend;
@@ -25,6 +25,18 @@ impl CrossPlatformScreenCapture {
         self.backend.get_display_resolution()
     }
 
+    /// Enumerates every attached display so the caller can offer the user a
+    /// choice of which one to mirror.
+    pub fn list_displays(&mut self) -> Result<Vec<DisplayInfo>, String> {
+        self.backend.list_displays()
+    }
+
+    /// Switches the capture source to the display with the given id. Takes
+    /// effect on the next `get_display_resolution`/`get_latest_frame` call.
+    pub fn select_display(&mut self, id: u32) -> Result<(), String> {
+        self.backend.select_display(id)
+    }
+
     pub fn start_capture(&mut self, window: Option<&winit::window::Window>) -> Result<(), String> {
         self.backend.start_capture(window)
     }
@@ -0,0 +1,1314 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use winit::window::Window;
+
+/// Maximum number of redaction rectangles the fragment shader accepts per frame.
+/// Kept small and fixed-size so the uniform buffer layout never changes shape.
+const MAX_REDACTION_RECTS: usize = 32;
+
+/// Blur sigma floor/ceiling a `RedactionRect::strength` of 0.0..1.0 maps to.
+const MIN_BLUR_SIGMA: f32 = 1.0;
+const MAX_BLUR_SIGMA: f32 = 16.0;
+/// Sigma used for the blur passes before any `Blur`-styled rect has set one.
+const DEFAULT_BLUR_SIGMA: f32 = 4.0;
+
+/// How many recent samples each profiler counter keeps.
+const PROFILER_HISTORY_LEN: usize = 120;
+
+/// Frame budget used to fix the right edge of the GPU-time bar/graph, so a
+/// present that overruns it is visibly pinned at full width instead of the
+/// bar silently auto-scaling to hide the spike.
+const FRAME_BUDGET_MS: f32 = 16.6;
+
+/// Names of the counters tracked by the diagnostics overlay, in display order.
+const PROFILER_COUNTERS: [&str; 5] = [
+    "ocr_latency_ms",
+    "frames_since_ocr",
+    "redaction_boxes",
+    "cpu_frame_ms",
+    "gpu_present_ms",
+];
+
+/// Cycles through the on-screen diagnostics overlay: hidden, numeric
+/// average+max readout bars, or a rolling line graph per counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayMode {
+    Off,
+    Readout,
+    Graph,
+}
+
+impl OverlayMode {
+    fn next(self) -> Self {
+        match self {
+            OverlayMode::Off => OverlayMode::Readout,
+            OverlayMode::Readout => OverlayMode::Graph,
+            OverlayMode::Graph => OverlayMode::Off,
+        }
+    }
+}
+
+/// A single named counter with a fixed-size ring buffer of recent samples.
+/// Samples are `None` on frames where nothing was measured (e.g. OCR latency
+/// between OCR passes), so the overlay can skip gaps instead of plotting a
+/// misleading zero.
+struct ProfilerCounter {
+    name: &'static str,
+    samples: VecDeque<Option<f32>>,
+}
+
+impl ProfilerCounter {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            samples: VecDeque::from(vec![None; PROFILER_HISTORY_LEN]),
+        }
+    }
+
+    fn push(&mut self, sample: Option<f32>) {
+        self.samples.push_back(sample);
+        if self.samples.len() > PROFILER_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+    }
+
+    fn average(&self) -> f32 {
+        let known: Vec<f32> = self.samples.iter().filter_map(|s| *s).collect();
+        if known.is_empty() {
+            0.0
+        } else {
+            known.iter().sum::<f32>() / known.len() as f32
+        }
+    }
+
+    fn max(&self) -> f32 {
+        self.samples.iter().filter_map(|s| *s).fold(0.0, f32::max)
+    }
+
+    /// Scale this counter's bar/graph is normalized against. The GPU present
+    /// counter always scales to the frame budget so overruns are visible as
+    /// clipping; everything else auto-scales to its own rolling max.
+    fn display_scale(&self) -> f32 {
+        if self.name == "gpu_present_ms" {
+            FRAME_BUDGET_MS
+        } else {
+            self.max().max(1.0)
+        }
+    }
+}
+
+/// Rolling counters feeding the diagnostics overlay: OCR latency,
+/// frames-since-last-OCR, active redaction box count, per-frame CPU time, and
+/// GPU present time.
+struct Profiler {
+    counters: Vec<ProfilerCounter>,
+}
+
+impl Profiler {
+    fn new() -> Self {
+        Self {
+            counters: PROFILER_COUNTERS.iter().map(|name| ProfilerCounter::new(name)).collect(),
+        }
+    }
+
+    fn push(&mut self, name: &str, sample: Option<f32>) {
+        if let Some(counter) = self.counters.iter_mut().find(|c| c.name == name) {
+            counter.push(sample);
+        }
+    }
+}
+
+/// A vertex for the flat-colored overlay geometry (bars and line graphs).
+/// Position is in normalized device coordinates.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct OverlayVertex {
+    position: [f32; 2],
+    color: [f32; 3],
+}
+
+/// Enough vertices for every counter's readout bars (6 verts/quad * 2 quads)
+/// or full-history line graph, whichever mode is active.
+const OVERLAY_VERTEX_CAPACITY: usize = PROFILER_COUNTERS.len() * PROFILER_HISTORY_LEN;
+
+const OVERLAY_VERTEX_SHADER: &str = r#"
+struct VertexIn {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexIn) -> VertexOut {
+    var out: VertexOut;
+    out.position = vec4<f32>(in.position, 0.0, 1.0);
+    out.color = in.color;
+    return out;
+}
+"#;
+
+const OVERLAY_FRAGMENT_SHADER: &str = r#"
+@fragment
+fn fs_main(@location(0) color: vec3<f32>) -> @location(0) vec4<f32> {
+    return vec4<f32>(color, 0.85);
+}
+"#;
+
+/// Appends two triangles covering `[left, right] x [bottom, top]` (all in
+/// normalized device coordinates) to `vertices`, used to draw overlay bars.
+fn push_quad(vertices: &mut Vec<OverlayVertex>, left: f32, top: f32, right: f32, bottom: f32, color: [f32; 3]) {
+    let top_left = OverlayVertex { position: [left, top], color };
+    let top_right = OverlayVertex { position: [right, top], color };
+    let bottom_left = OverlayVertex { position: [left, bottom], color };
+    let bottom_right = OverlayVertex { position: [right, bottom], color };
+
+    vertices.extend_from_slice(&[top_left, bottom_left, top_right, top_right, bottom_left, bottom_right]);
+}
+
+/// Redaction style applied to a rectangle, matched against the `style` field
+/// in the fragment shader (kept in sync with `STYLE_*` constants in the WGSL below).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionStyle {
+    /// Solid fill, same visual result as the old CPU black-box behavior.
+    Fill,
+    /// Snaps the sample coordinate to a coarse grid before sampling the source texture.
+    Mosaic,
+    /// Separable Gaussian blur, pre-baked into an intermediate texture.
+    Blur,
+}
+
+impl RedactionStyle {
+    fn as_gpu_tag(self) -> u32 {
+        match self {
+            RedactionStyle::Fill => 0,
+            RedactionStyle::Mosaic => 1,
+            RedactionStyle::Blur => 2,
+        }
+    }
+}
+
+/// wgpu requires buffer rows copied out of a texture to be padded to a
+/// multiple of this many bytes.
+const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+/// Optional region (in output pixels) to crop a capture to, so a user can
+/// export just one part of the redacted frame instead of the whole display.
+#[derive(Debug, Clone, Copy)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// One region to redact, in normalized (0..1) texture coordinates.
+#[derive(Debug, Clone, Copy)]
+pub struct RedactionRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub style: RedactionStyle,
+    /// Mosaic block size (normalized) or blur sigma (normalized), depending on `style`.
+    pub strength: f32,
+}
+
+/// GPU-side layout for a single redaction rect. Must stay in sync with the WGSL struct.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct RedactionRectGpu {
+    rect: [f32; 4],
+    style: u32,
+    strength: f32,
+    _padding: [f32; 2],
+}
+
+/// GPU-side uniform block: a fixed-size array of rects plus how many are active.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct RedactionUniform {
+    rects: [RedactionRectGpu; MAX_REDACTION_RECTS],
+    rect_count: u32,
+    _padding: [u32; 3],
+}
+
+impl Default for RedactionUniform {
+    fn default() -> Self {
+        Self {
+            rects: [RedactionRectGpu {
+                rect: [0.0; 4],
+                style: 0,
+                strength: 0.0,
+                _padding: [0.0; 2],
+            }; MAX_REDACTION_RECTS],
+            rect_count: 0,
+            _padding: [0; 3],
+        }
+    }
+}
+
+/// Fullscreen-quad vertex shader shared by every pass below: it needs no vertex
+/// buffer, just `@builtin(vertex_index)` to place the three corners of a
+/// clip-space triangle that covers the viewport.
+const FULLSCREEN_VERTEX_SHADER: &str = r#"
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOut {
+    var out: VertexOut;
+    let x = f32((vertex_index << 1u) & 2u);
+    let y = f32(vertex_index & 2u);
+    out.position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, y);
+    return out;
+}
+"#;
+
+/// Separable Gaussian blur fragment shader, one direction per pass. `direction`
+/// is (1,0) for the horizontal pass and (0,1) for the vertical pass.
+const BLUR_FRAGMENT_SHADER: &str = r#"
+@group(0) @binding(0) var source_texture: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+
+struct BlurParams {
+    direction: vec2<f32>,
+    sigma: f32,
+    _padding: f32,
+};
+@group(0) @binding(2) var<uniform> params: BlurParams;
+
+const TAPS: i32 = 9;
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let texel = params.direction / vec2<f32>(textureDimensions(source_texture));
+    var total = vec4<f32>(0.0);
+    var weight_sum = 0.0;
+    let half_taps = TAPS / 2;
+    for (var i = -half_taps; i <= half_taps; i = i + 1) {
+        let offset = f32(i);
+        let weight = exp(-(offset * offset) / (2.0 * params.sigma * params.sigma));
+        total = total + textureSample(source_texture, source_sampler, uv + texel * offset) * weight;
+        weight_sum = weight_sum + weight;
+    }
+    return total / weight_sum;
+}
+"#;
+
+/// Final composite pass: picks, per fragment, between the raw source, a
+/// mosaic-sampled source, or the pre-blurred texture depending on which
+/// (if any) redaction rect the fragment falls inside.
+const COMPOSITE_FRAGMENT_SHADER: &str = r#"
+const STYLE_FILL: u32 = 0u;
+const STYLE_MOSAIC: u32 = 1u;
+const STYLE_BLUR: u32 = 2u;
+
+struct RedactionRect {
+    rect: vec4<f32>, // x, y, width, height in normalized coords
+    style: u32,
+    strength: f32,
+    _padding: vec2<f32>,
+};
+
+struct RedactionUniform {
+    rects: array<RedactionRect, 32>,
+    rect_count: u32,
+};
+
+@group(0) @binding(0) var source_texture: texture_2d<f32>;
+@group(0) @binding(1) var blurred_texture: texture_2d<f32>;
+@group(0) @binding(2) var tex_sampler: sampler;
+@group(0) @binding(3) var<uniform> redaction: RedactionUniform;
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    for (var i = 0u; i < redaction.rect_count; i = i + 1u) {
+        let r = redaction.rects[i];
+        if (uv.x >= r.rect.x && uv.x <= r.rect.x + r.rect.z &&
+            uv.y >= r.rect.y && uv.y <= r.rect.y + r.rect.w) {
+            if (r.style == STYLE_MOSAIC) {
+                let size = vec2<f32>(textureDimensions(source_texture));
+                let block = max(r.strength, 1.0 / size.x);
+                let snapped = floor(uv / block) * block;
+                return textureSample(source_texture, tex_sampler, snapped);
+            } else if (r.style == STYLE_BLUR) {
+                return textureSample(blurred_texture, tex_sampler, uv);
+            } else {
+                return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+            }
+        }
+    }
+    return textureSample(source_texture, tex_sampler, uv);
+}
+"#;
+
+/// GpuRenderer: owns the wgpu device/surface and runs the capture-to-screen
+/// render pipeline, including the redaction passes.
+///
+/// Pipeline per frame: upload the captured frame as `source_texture`, run two
+/// blur passes (horizontal then vertical) into ping-ponged intermediate
+/// textures so blur-style rects have something to sample from, then run the
+/// composite pass against the swapchain surface.
+pub struct GpuRenderer {
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    config: wgpu::SurfaceConfiguration,
+    size: winit::dpi::PhysicalSize<u32>,
+
+    source_texture: wgpu::Texture,
+    source_view: wgpu::TextureView,
+    blur_h_view: wgpu::TextureView,
+    blur_v_view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+
+    blur_h_pipeline: wgpu::RenderPipeline,
+    blur_v_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+
+    blur_h_params_buffer: wgpu::Buffer,
+    blur_v_params_buffer: wgpu::Buffer,
+    redaction_buffer: wgpu::Buffer,
+
+    blur_h_bind_group: wgpu::BindGroup,
+    blur_v_bind_group: wgpu::BindGroup,
+    composite_bind_group: wgpu::BindGroup,
+
+    redaction_rects: Vec<RedactionRect>,
+
+    profiler: Profiler,
+    overlay_mode: OverlayMode,
+    overlay_bar_pipeline: wgpu::RenderPipeline,
+    overlay_line_pipeline: wgpu::RenderPipeline,
+    overlay_vertex_buffer: wgpu::Buffer,
+}
+
+impl GpuRenderer {
+    /// Creates the full rendering pipeline: device/surface setup, the
+    /// capture texture, and the blur + composite render passes.
+    pub async fn new(window: Arc<Window>, width: u32, height: u32) -> Self {
+        let instance = wgpu::Instance::default();
+        let surface = instance.create_surface(window.clone()).unwrap();
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await
+            .expect("Failed to find a suitable GPU adapter");
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await
+            .expect("Failed to create GPU device");
+
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = surface_caps
+            .formats
+            .iter()
+            .copied()
+            .find(|f| f.is_srgb())
+            .unwrap_or(surface_caps.formats[0]);
+
+        let size = winit::dpi::PhysicalSize::new(width, height);
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&device, &config);
+
+        let source_texture = Self::create_frame_texture(&device, size.width, size.height, "source");
+        let source_view = source_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let blur_h_texture = Self::create_frame_texture(&device, size.width, size.height, "blur-h");
+        let blur_h_view = blur_h_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let blur_v_texture = Self::create_frame_texture(&device, size.width, size.height, "blur-v");
+        let blur_v_view = blur_v_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("redaction-sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let blur_h_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("blur-h-params"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let blur_v_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("blur-v-params"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let redaction_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("redaction-rects"),
+            size: std::mem::size_of::<RedactionUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let vertex_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fullscreen-vertex"),
+            source: wgpu::ShaderSource::Wgsl(FULLSCREEN_VERTEX_SHADER.into()),
+        });
+        let blur_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("blur-fragment"),
+            source: wgpu::ShaderSource::Wgsl(BLUR_FRAGMENT_SHADER.into()),
+        });
+        let composite_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("composite-fragment"),
+            source: wgpu::ShaderSource::Wgsl(COMPOSITE_FRAGMENT_SHADER.into()),
+        });
+
+        let blur_bind_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("blur-bind-layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let composite_bind_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("composite-bind-layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let blur_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("blur-pipeline-layout"),
+            bind_group_layouts: &[&blur_bind_layout],
+            push_constant_ranges: &[],
+        });
+        let composite_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("composite-pipeline-layout"),
+            bind_group_layouts: &[&composite_bind_layout],
+            push_constant_ranges: &[],
+        });
+
+        let make_blur_pipeline = |layout: &wgpu::PipelineLayout, label: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: &vertex_module,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &blur_module,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let blur_h_pipeline = make_blur_pipeline(&blur_pipeline_layout, "blur-h-pipeline");
+        let blur_v_pipeline = make_blur_pipeline(&blur_pipeline_layout, "blur-v-pipeline");
+
+        let composite_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("composite-pipeline"),
+            layout: Some(&composite_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &vertex_module,
+                entry_point: "vs_main",
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &composite_module,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let blur_h_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blur-h-bind-group"),
+            layout: &blur_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&source_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: blur_h_params_buffer.as_entire_binding() },
+            ],
+        });
+        let blur_v_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blur-v-bind-group"),
+            layout: &blur_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&blur_h_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: blur_v_params_buffer.as_entire_binding() },
+            ],
+        });
+        let composite_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("composite-bind-group"),
+            layout: &composite_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&source_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&blur_v_view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(&sampler) },
+                wgpu::BindGroupEntry { binding: 3, resource: redaction_buffer.as_entire_binding() },
+            ],
+        });
+
+        queue.write_buffer(
+            &blur_h_params_buffer,
+            0,
+            bytemuck::bytes_of(&[1.0f32, 0.0, DEFAULT_BLUR_SIGMA, 0.0]),
+        );
+        queue.write_buffer(
+            &blur_v_params_buffer,
+            0,
+            bytemuck::bytes_of(&[0.0f32, 1.0, DEFAULT_BLUR_SIGMA, 0.0]),
+        );
+
+        let overlay_vertex_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("overlay-vertex"),
+            source: wgpu::ShaderSource::Wgsl(OVERLAY_VERTEX_SHADER.into()),
+        });
+        let overlay_fragment_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("overlay-fragment"),
+            source: wgpu::ShaderSource::Wgsl(OVERLAY_FRAGMENT_SHADER.into()),
+        });
+        let overlay_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("overlay-pipeline-layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let overlay_vertex_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<OverlayVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+            ],
+        };
+        let make_overlay_pipeline = |topology: wgpu::PrimitiveTopology, label: &str| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&overlay_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &overlay_vertex_module,
+                    entry_point: "vs_main",
+                    buffers: &[overlay_vertex_layout.clone()],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &overlay_fragment_module,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: config.format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let overlay_bar_pipeline = make_overlay_pipeline(wgpu::PrimitiveTopology::TriangleList, "overlay-bar-pipeline");
+        let overlay_line_pipeline = make_overlay_pipeline(wgpu::PrimitiveTopology::LineStrip, "overlay-line-pipeline");
+
+        let overlay_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("overlay-vertex-buffer"),
+            size: (OVERLAY_VERTEX_CAPACITY * std::mem::size_of::<OverlayVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            surface,
+            device,
+            queue,
+            config,
+            size,
+            source_texture,
+            source_view,
+            blur_h_view,
+            blur_v_view,
+            sampler,
+            blur_h_pipeline,
+            blur_v_pipeline,
+            composite_pipeline,
+            blur_h_params_buffer,
+            blur_v_params_buffer,
+            redaction_buffer,
+            blur_h_bind_group,
+            blur_v_bind_group,
+            composite_bind_group,
+            redaction_rects: Vec::new(),
+            profiler: Profiler::new(),
+            overlay_mode: OverlayMode::Off,
+            overlay_bar_pipeline,
+            overlay_line_pipeline,
+            overlay_vertex_buffer,
+        }
+    }
+
+    fn create_frame_texture(device: &wgpu::Device, width: u32, height: u32, label: &str) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    }
+
+    /// Sets the redaction rectangles the composite pass should apply this frame.
+    /// Rects beyond `MAX_REDACTION_RECTS` are dropped rather than resizing the
+    /// uniform buffer, matching the detector's cap on tracked matches.
+    pub fn set_redaction_rects(&mut self, rects: &[RedactionRect]) {
+        self.redaction_rects = rects.iter().take(MAX_REDACTION_RECTS).copied().collect();
+
+        let mut uniform = RedactionUniform::default();
+        uniform.rect_count = self.redaction_rects.len() as u32;
+        for (i, rect) in self.redaction_rects.iter().enumerate() {
+            uniform.rects[i] = RedactionRectGpu {
+                rect: [rect.x, rect.y, rect.width, rect.height],
+                style: rect.style.as_gpu_tag(),
+                strength: rect.strength,
+                _padding: [0.0; 2],
+            };
+        }
+        self.queue
+            .write_buffer(&self.redaction_buffer, 0, bytemuck::bytes_of(&uniform));
+
+        // The blur passes run once per frame over the whole source texture
+        // (not per-rect), so there's only one sigma to pick: take it from
+        // the first `Blur`-styled rect, since that's the one whose strength
+        // a caller actually means to control.
+        let sigma = self
+            .redaction_rects
+            .iter()
+            .find(|rect| rect.style == RedactionStyle::Blur)
+            .map(|rect| (rect.strength * MAX_BLUR_SIGMA).max(MIN_BLUR_SIGMA))
+            .unwrap_or(DEFAULT_BLUR_SIGMA);
+        self.write_blur_params(sigma);
+    }
+
+    /// Whether any current rect is styled `Blur`, i.e. whether the blur
+    /// passes have anything to contribute to this frame's composite.
+    fn has_blur_rect(&self) -> bool {
+        self.redaction_rects
+            .iter()
+            .any(|rect| rect.style == RedactionStyle::Blur)
+    }
+
+    /// Writes the shared sigma both blur passes sample with (horizontal pass
+    /// first, vertical pass second - `direction` is what tells them apart).
+    fn write_blur_params(&self, sigma: f32) {
+        self.queue.write_buffer(
+            &self.blur_h_params_buffer,
+            0,
+            bytemuck::bytes_of(&[1.0f32, 0.0, sigma, 0.0]),
+        );
+        self.queue.write_buffer(
+            &self.blur_v_params_buffer,
+            0,
+            bytemuck::bytes_of(&[0.0f32, 1.0, sigma, 0.0]),
+        );
+    }
+
+    /// Uploads a captured RGBA frame as the source texture for this frame's render.
+    pub fn update_texture(&mut self, rgba_buffer: &[u8]) {
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.source_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            rgba_buffer,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * self.size.width),
+                rows_per_image: Some(self.size.height),
+            },
+            wgpu::Extent3d {
+                width: self.size.width,
+                height: self.size.height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Runs the blur passes (skipped entirely when no rect needs them) and
+    /// the composite pass, then presents the frame.
+    pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let present_started_at = std::time::Instant::now();
+
+        let output = self.surface.get_current_texture()?;
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("redaction-encoder"),
+            });
+
+        if self.has_blur_rect() {
+            let mut blur_h_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("blur-h-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.blur_h_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            blur_h_pass.set_pipeline(&self.blur_h_pipeline);
+            blur_h_pass.set_bind_group(0, &self.blur_h_bind_group, &[]);
+            blur_h_pass.draw(0..3, 0..1);
+
+            let mut blur_v_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("blur-v-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.blur_v_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            blur_v_pass.set_pipeline(&self.blur_v_pipeline);
+            blur_v_pass.set_bind_group(0, &self.blur_v_bind_group, &[]);
+            blur_v_pass.draw(0..3, 0..1);
+        }
+
+        {
+            let mut composite_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("composite-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            composite_pass.set_pipeline(&self.composite_pipeline);
+            composite_pass.set_bind_group(0, &self.composite_bind_group, &[]);
+            composite_pass.draw(0..3, 0..1);
+        }
+
+        if self.overlay_mode != OverlayMode::Off {
+            self.render_overlay(&mut encoder, &view);
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+        output.present();
+
+        let present_ms = present_started_at.elapsed().as_secs_f32() * 1000.0;
+        self.profiler.push("gpu_present_ms", Some(present_ms));
+
+        Ok(())
+    }
+
+    /// Records a sample for a named profiler counter. Pass `None` on frames
+    /// where nothing was measured (e.g. OCR latency between OCR passes) so
+    /// the overlay can skip the gap instead of plotting a false zero.
+    pub fn push_counter_sample(&mut self, name: &str, sample: Option<f32>) {
+        self.profiler.push(name, sample);
+    }
+
+    /// Advances the diagnostics overlay to its next mode: hidden, readout
+    /// bars, then rolling line graphs, then back to hidden.
+    pub fn cycle_overlay_mode(&mut self) {
+        self.overlay_mode = self.overlay_mode.next();
+        println!("📊 Diagnostics overlay: {:?}", self.overlay_mode);
+    }
+
+    /// Builds this frame's overlay geometry (readout bars or line graphs,
+    /// depending on the active mode) and draws it on top of the already
+    /// composited frame.
+    fn render_overlay(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        let vertices = match self.overlay_mode {
+            OverlayMode::Off => return,
+            OverlayMode::Readout => Self::build_readout_vertices(&self.profiler),
+            OverlayMode::Graph => Self::build_graph_vertices(&self.profiler),
+        };
+
+        if vertices.is_empty() {
+            return;
+        }
+
+        self.queue
+            .write_buffer(&self.overlay_vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+
+        let mut overlay_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("overlay-pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        match self.overlay_mode {
+            OverlayMode::Readout => {
+                overlay_pass.set_pipeline(&self.overlay_bar_pipeline);
+                overlay_pass.set_vertex_buffer(0, self.overlay_vertex_buffer.slice(..));
+                overlay_pass.draw(0..vertices.len() as u32, 0..1);
+            }
+            OverlayMode::Graph => {
+                overlay_pass.set_pipeline(&self.overlay_line_pipeline);
+                let per_counter = PROFILER_HISTORY_LEN as u32;
+                for (i, _) in self.profiler.counters.iter().enumerate() {
+                    overlay_pass.set_vertex_buffer(0, self.overlay_vertex_buffer.slice(..));
+                    let start = i as u32 * per_counter;
+                    overlay_pass.draw(start..start + per_counter, 0..1);
+                }
+            }
+            OverlayMode::Off => {}
+        }
+    }
+
+    /// Lays counters out as stacked horizontal bars in the top-right corner:
+    /// one bar for the rolling average, one thin tick for the rolling max,
+    /// each scaled by `ProfilerCounter::display_scale`.
+    fn build_readout_vertices(profiler: &Profiler) -> Vec<OverlayVertex> {
+        const PANEL_RIGHT: f32 = 0.98;
+        const PANEL_TOP: f32 = 0.98;
+        const BAR_WIDTH: f32 = 0.4;
+        const BAR_HEIGHT: f32 = 0.04;
+        const BAR_GAP: f32 = 0.01;
+        const AVERAGE_COLOR: [f32; 3] = [0.2, 0.8, 0.4];
+        const MAX_COLOR: [f32; 3] = [0.9, 0.3, 0.2];
+
+        let mut vertices = Vec::new();
+
+        for (row, counter) in profiler.counters.iter().enumerate() {
+            let top = PANEL_TOP - row as f32 * (BAR_HEIGHT + BAR_GAP);
+            let bottom = top - BAR_HEIGHT;
+            let left = PANEL_RIGHT - BAR_WIDTH;
+
+            let scale = counter.display_scale();
+            let average_fraction = (counter.average() / scale).clamp(0.0, 1.0);
+            let max_fraction = (counter.max() / scale).clamp(0.0, 1.0);
+
+            push_quad(&mut vertices, left, top, left + BAR_WIDTH * average_fraction, bottom, AVERAGE_COLOR);
+
+            let tick_x = left + BAR_WIDTH * max_fraction;
+            push_quad(&mut vertices, tick_x - 0.002, top, tick_x + 0.002, bottom, MAX_COLOR);
+        }
+
+        vertices
+    }
+
+    /// Lays each counter's rolling history out as a line graph in its own
+    /// horizontal row, holding the last known value across `None` samples so
+    /// a gap reads as a flat segment rather than a drop to zero.
+    fn build_graph_vertices(profiler: &Profiler) -> Vec<OverlayVertex> {
+        const PANEL_RIGHT: f32 = 0.98;
+        const PANEL_TOP: f32 = 0.98;
+        const ROW_WIDTH: f32 = 0.4;
+        const ROW_HEIGHT: f32 = 0.1;
+        const ROW_GAP: f32 = 0.02;
+        const LINE_COLOR: [f32; 3] = [0.3, 0.7, 1.0];
+
+        let mut vertices = Vec::new();
+
+        for (row, counter) in profiler.counters.iter().enumerate() {
+            let top = PANEL_TOP - row as f32 * (ROW_HEIGHT + ROW_GAP);
+            let bottom = top - ROW_HEIGHT;
+            let left = PANEL_RIGHT - ROW_WIDTH;
+            let scale = counter.display_scale();
+
+            let mut last_value = 0.0;
+            let samples_len = counter.samples.len().max(1);
+            for (i, sample) in counter.samples.iter().enumerate() {
+                if let Some(value) = sample {
+                    last_value = *value;
+                }
+                let fraction = (last_value / scale).clamp(0.0, 1.0);
+                let x = left + ROW_WIDTH * (i as f32 / (samples_len - 1).max(1) as f32);
+                let y = bottom + ROW_HEIGHT * fraction;
+                vertices.push(OverlayVertex { position: [x, y], color: LINE_COLOR });
+            }
+        }
+
+        vertices
+    }
+
+    /// Resizes the surface and every intermediate texture to match.
+    pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+        if new_size.width == 0 || new_size.height == 0 {
+            return;
+        }
+
+        self.size = new_size;
+        self.config.width = new_size.width;
+        self.config.height = new_size.height;
+        self.surface.configure(&self.device, &self.config);
+
+        self.source_texture = Self::create_frame_texture(&self.device, new_size.width, new_size.height, "source");
+        self.source_view = self
+            .source_texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let blur_h_texture = Self::create_frame_texture(&self.device, new_size.width, new_size.height, "blur-h");
+        self.blur_h_view = blur_h_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let blur_v_texture = Self::create_frame_texture(&self.device, new_size.width, new_size.height, "blur-v");
+        self.blur_v_view = blur_v_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Bind groups hold texture views by reference to the old textures, so
+        // they need to be rebuilt against the freshly recreated ones.
+        self.rebuild_bind_groups();
+    }
+
+    fn rebuild_bind_groups(&mut self) {
+        let blur_bind_layout = self.blur_h_pipeline.get_bind_group_layout(0);
+        let composite_bind_layout = self.composite_pipeline.get_bind_group_layout(0);
+
+        self.blur_h_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blur-h-bind-group"),
+            layout: &blur_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&self.source_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: self.blur_h_params_buffer.as_entire_binding() },
+            ],
+        });
+        self.blur_v_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blur-v-bind-group"),
+            layout: &blur_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&self.blur_h_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: self.blur_v_params_buffer.as_entire_binding() },
+            ],
+        });
+        self.composite_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("composite-bind-group"),
+            layout: &composite_bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&self.source_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&self.blur_v_view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(&self.sampler) },
+                wgpu::BindGroupEntry { binding: 3, resource: self.redaction_buffer.as_entire_binding() },
+            ],
+        });
+    }
+
+    /// Current surface size, used by callers that need to re-request a resize.
+    pub fn size(&self) -> winit::dpi::PhysicalSize<u32> {
+        self.size
+    }
+
+    /// Renders the current redacted frame (same blur + composite passes used
+    /// for the on-screen surface) into an offscreen texture, reads it back,
+    /// and writes it to `path` as a PNG. Optionally crops to `crop` first so
+    /// a user can export just one region of the display.
+    pub fn capture_to_png(&mut self, path: &str, crop: Option<CropRect>) -> Result<(), String> {
+        let crop = crop.unwrap_or(CropRect {
+            x: 0,
+            y: 0,
+            width: self.size.width,
+            height: self.size.height,
+        });
+
+        // Rendered at a hardcoded Rgba8UnormSrgb (same as `create_frame_texture`,
+        // not `self.config.format`) so the readback below can feed straight into
+        // `image::RgbaImage` - the surface's negotiated format is often
+        // Bgra8UnormSrgb on DX12/Metal/Vulkan, which would otherwise swap red
+        // and blue in every exported PNG.
+        let capture_texture =
+            Self::create_frame_texture(&self.device, self.size.width, self.size.height, "capture-target");
+        let capture_view = capture_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("capture-encoder"),
+            });
+
+        if self.has_blur_rect() {
+            let mut blur_h_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("capture-blur-h-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.blur_h_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            blur_h_pass.set_pipeline(&self.blur_h_pipeline);
+            blur_h_pass.set_bind_group(0, &self.blur_h_bind_group, &[]);
+            blur_h_pass.draw(0..3, 0..1);
+
+            let mut blur_v_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("capture-blur-v-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.blur_v_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            blur_v_pass.set_pipeline(&self.blur_v_pipeline);
+            blur_v_pass.set_bind_group(0, &self.blur_v_bind_group, &[]);
+            blur_v_pass.draw(0..3, 0..1);
+        }
+
+        {
+            let mut composite_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("capture-composite-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &capture_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            composite_pass.set_pipeline(&self.composite_pipeline);
+            composite_pass.set_bind_group(0, &self.composite_bind_group, &[]);
+            composite_pass.draw(0..3, 0..1);
+        }
+
+        let bytes_per_pixel = 4u32;
+        let unpadded_bytes_per_row = self.size.width * bytes_per_pixel;
+        let padded_bytes_per_row =
+            unpadded_bytes_per_row.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let readback_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("capture-readback"),
+            size: (padded_bytes_per_row * self.size.height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &capture_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &readback_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(self.size.height),
+                },
+            },
+            wgpu::Extent3d {
+                width: self.size.width,
+                height: self.size.height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = readback_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|e| format!("Failed to receive mapped buffer: {}", e))?
+            .map_err(|e| format!("Failed to map readback buffer: {}", e))?;
+
+        let padded = slice.get_mapped_range();
+        let mut unpadded = Vec::with_capacity((unpadded_bytes_per_row * self.size.height) as usize);
+        for row in padded.chunks_exact(padded_bytes_per_row as usize) {
+            unpadded.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        drop(padded);
+        readback_buffer.unmap();
+
+        let full_image =
+            image::RgbaImage::from_raw(self.size.width, self.size.height, unpadded)
+                .ok_or("Failed to build image buffer from readback bytes")?;
+
+        let cropped = image::imageops::crop_imm(
+            &full_image,
+            crop.x.min(self.size.width.saturating_sub(1)),
+            crop.y.min(self.size.height.saturating_sub(1)),
+            crop.width.min(self.size.width),
+            crop.height.min(self.size.height),
+        )
+        .to_image();
+
+        cropped
+            .save(path)
+            .map_err(|e| format!("Failed to save capture to {}: {}", path, e))?;
+
+        println!("📸 Saved redacted frame capture to {}", path);
+        Ok(())
+    }
+
+    /// Solid test pattern used before the first real frame arrives.
+    pub fn create_test_pattern(&self) -> Vec<u8> {
+        let mut buffer = vec![0u8; (self.size.width * self.size.height * 4) as usize];
+        for chunk in buffer.chunks_exact_mut(4) {
+            chunk[0] = 32;
+            chunk[1] = 32;
+            chunk[2] = 32;
+            chunk[3] = 255;
+        }
+        buffer
+    }
+}
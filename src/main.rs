@@ -1,5 +1,6 @@
 mod cross_platform_capture;
 mod gpu_renderer;
+mod headless;
 mod pixel_conversion;
 mod platform;
 mod safe_mirror;
@@ -10,8 +11,9 @@ use crate::safe_mirror::SafeMirror;
 use std::sync::Arc;
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
+    event::{ElementState, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop},
+    keyboard::{KeyCode, PhysicalKey},
     window::{Window, WindowId},
 };
 
@@ -34,6 +36,33 @@ impl ApplicationHandler for App {
         let mut screen_capture = crate::cross_platform_capture::CrossPlatformScreenCapture::new()
             .expect("Failed to create screen capture");
 
+        // Enumerate attached displays and pick one to mirror. Defaults to the
+        // first display; set CLOAKSHARE_DISPLAY_ID to mirror a specific one
+        // on multi-monitor setups.
+        match screen_capture.list_displays() {
+            Ok(displays) => {
+                for display in &displays {
+                    println!(
+                        "🖥️  Display {}: {} ({}x{} @ {:.2}x, origin {:?})",
+                        display.id, display.name, display.resolution.width,
+                        display.resolution.height, display.scale_factor, display.origin
+                    );
+                }
+
+                let selected_display_id = std::env::var("CLOAKSHARE_DISPLAY_ID")
+                    .ok()
+                    .and_then(|value| value.parse::<u32>().ok())
+                    .or_else(|| displays.first().map(|display| display.id));
+
+                if let Some(id) = selected_display_id {
+                    if let Err(e) = screen_capture.select_display(id) {
+                        eprintln!("Failed to select display {}: {}", id, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to list displays: {}, using default", e),
+        }
+
         // Get display resolution for window sizing
         let resolution = screen_capture.get_display_resolution().unwrap_or_else(|e| {
             eprintln!("Failed to get display resolution: {}, using fallback", e);
@@ -85,6 +114,28 @@ impl ApplicationHandler for App {
                     safe_mirror.resize(physical_size);
                 }
 
+                // 'P' exports the current redacted frame to a PNG on disk,
+                // 'O' cycles the diagnostics overlay (off -> readout -> graph),
+                // 'R' cycles the redaction style (mosaic -> blur -> fill)
+                WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed => {
+                    if event.physical_key == PhysicalKey::Code(KeyCode::KeyP) {
+                        let timestamp = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        let path = format!("cloak_share_capture_{}.png", timestamp);
+
+                        match safe_mirror.capture_frame(&path, None) {
+                            Ok(_) => println!("📸 Saved capture to {}", path),
+                            Err(e) => eprintln!("Failed to save capture: {}", e),
+                        }
+                    } else if event.physical_key == PhysicalKey::Code(KeyCode::KeyO) {
+                        safe_mirror.cycle_overlay_mode();
+                    } else if event.physical_key == PhysicalKey::Code(KeyCode::KeyR) {
+                        safe_mirror.cycle_redaction_style();
+                    }
+                }
+
                 // System requests a redraw (60fps or when window needs updating)
                 WindowEvent::RedrawRequested => {
                     // Render the frame to the screen
@@ -115,8 +166,34 @@ impl ApplicationHandler for App {
     }
 }
 
-/// Main function: Entry point of the application
+/// Main function: Entry point of the application. Dispatches to one of two
+/// front-ends: the normal winit/wgpu windowed app, or `--headless` for
+/// processing a single frame off a disk with no surface creation at all
+/// (used for regression tests and scripted batch processing).
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.get(1).map(String::as_str) == Some("--headless") {
+        let input_path = args
+            .get(2)
+            .expect("Usage: cloakshare --headless <input-image> <output-image>");
+        let output_path = args
+            .get(3)
+            .expect("Usage: cloakshare --headless <input-image> <output-image>");
+
+        if let Err(e) = headless::run(input_path, output_path) {
+            eprintln!("Headless run failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    run_windowed_app();
+}
+
+/// Normal front-end: creates a winit window and GPU surface, then mirrors
+/// the selected display through the redaction pipeline at 60fps.
+fn run_windowed_app() {
     println!("Starting CloakShare Safe Mirror...");
 
     // Create the main event loop (handles window events, user input, etc.)
@@ -1,15 +1,16 @@
-use super::traits::{DisplayResolution, PlatformScreenCapture};
+use super::traits::{DisplayInfo, DisplayResolution, PlatformScreenCapture};
 use crate::screen_capture::FrameBuffer;
 use std::sync::Arc;
 use x11rb::connection::Connection;
 use x11rb::protocol::randr::ConnectionExt as _;
 use x11rb::rust_connection::RustConnection;
 
-/// Linux screen capture via XRandR for monitor resolution and X11's
+/// Linux screen capture via XRandR for monitor enumeration and X11's
 /// `GetImage` (or a PipeWire portal, on Wayland) for the actual frames.
 pub struct LinuxScreenCapture {
     connection: RustConnection,
     root: u32,
+    selected_output: Option<u32>,
     latest_frame: Arc<FrameBuffer>,
 }
 
@@ -22,11 +23,23 @@ impl PlatformScreenCapture for LinuxScreenCapture {
         Ok(Self {
             connection,
             root,
+            selected_output: None,
             latest_frame: Arc::new(FrameBuffer::new()),
         })
     }
 
     fn get_display_resolution(&mut self) -> Result<DisplayResolution, String> {
+        let displays = self.list_displays()?;
+        let selected = self
+            .selected_output
+            .and_then(|id| displays.iter().find(|d| d.id == id))
+            .or_else(|| displays.first())
+            .ok_or("No displays attached")?;
+
+        Ok(selected.resolution)
+    }
+
+    fn list_displays(&mut self) -> Result<Vec<DisplayInfo>, String> {
         let resources = self
             .connection
             .randr_get_screen_resources(self.root)
@@ -37,7 +50,7 @@ impl PlatformScreenCapture for LinuxScreenCapture {
         resources
             .crtcs
             .iter()
-            .find_map(|&crtc| {
+            .filter_map(|&crtc| {
                 let info = self
                     .connection
                     .randr_get_crtc_info(crtc, resources.config_timestamp)
@@ -49,18 +62,40 @@ impl PlatformScreenCapture for LinuxScreenCapture {
                     return None; // Disabled CRTC, not an active monitor
                 }
 
-                Some(DisplayResolution {
-                    width: info.width as u32,
-                    height: info.height as u32,
+                // `crtc` is the real RandR XID for this CRTC, not a recomputed
+                // enumeration index - it stays valid as a `select_display` key
+                // even if a later `list_displays` call sees a different order.
+                Some(DisplayInfo {
+                    id: crtc,
+                    name: format!("CRTC {}", crtc),
+                    resolution: DisplayResolution {
+                        width: info.width as u32,
+                        height: info.height as u32,
+                    },
+                    scale_factor: 1.0,
+                    origin: (info.x as i32, info.y as i32),
                 })
             })
-            .ok_or_else(|| "No displays attached".to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(Ok)
+            .collect()
+    }
+
+    fn select_display(&mut self, id: u32) -> Result<(), String> {
+        let displays = self.list_displays()?;
+        if !displays.iter().any(|d| d.id == id) {
+            return Err(format!("No display with id {}", id));
+        }
+
+        self.selected_output = Some(id);
+        Ok(())
     }
 
     fn start_capture(&mut self, _window: Option<&winit::window::Window>) -> Result<(), String> {
-        // A real implementation polls XGetImage on the primary CRTC's bounds
-        // (or negotiates a PipeWire stream under Wayland), converts BGRA
-        // frames with `pixel_conversion::bgra_to_rgba`, and stores the
+        // A real implementation polls XGetImage on the selected CRTC's
+        // bounds (or negotiates a PipeWire stream under Wayland), converts
+        // BGRA frames with `pixel_conversion::bgra_to_rgba`, and stores the
         // result via `self.latest_frame.store(..)`.
         println!("🐧 Started Linux screen capture");
         Ok(())
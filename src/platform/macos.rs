@@ -1,11 +1,11 @@
-use super::traits::{DisplayResolution, PlatformScreenCapture};
+use super::traits::{DisplayInfo, DisplayResolution, PlatformScreenCapture};
 use crate::screen_capture::FrameBuffer;
-use core_graphics::display::CGDisplay;
+use core_graphics::display::{CGDisplay, CGMainDisplayID};
 use std::sync::Arc;
 
-/// macOS screen capture backed by `CGDisplayStream` on the main display.
-/// Frames land in `latest_frame` via the stream's callback and are handed
-/// to the renderer on the next `get_latest_frame`.
+/// macOS screen capture backed by `CGDisplayStream`, one active stream per
+/// selected display. Frames land in `latest_frame` via the stream's
+/// callback and are handed to the renderer on the next `get_latest_frame`.
 pub struct MacosScreenCapture {
     selected_display: CGDisplay,
     latest_frame: Arc<FrameBuffer>,
@@ -31,6 +31,49 @@ impl PlatformScreenCapture for MacosScreenCapture {
         })
     }
 
+    fn list_displays(&mut self) -> Result<Vec<DisplayInfo>, String> {
+        let active_displays =
+            CGDisplay::active_displays().map_err(|e| format!("Failed to enumerate displays: {:?}", e))?;
+
+        active_displays
+            .into_iter()
+            .map(|id| {
+                let display = CGDisplay::new(id);
+                let mode = display
+                    .display_mode()
+                    .ok_or_else(|| format!("Failed to read display mode for display {}", id))?;
+                let bounds = display.bounds();
+
+                Ok(DisplayInfo {
+                    id,
+                    name: if id == unsafe { CGMainDisplayID() } {
+                        "Built-in / Main Display".to_string()
+                    } else {
+                        format!("Display {}", id)
+                    },
+                    resolution: DisplayResolution {
+                        width: mode.pixel_width() as u32,
+                        height: mode.pixel_height() as u32,
+                    },
+                    scale_factor: mode.pixel_width() as f64 / mode.width() as f64,
+                    origin: (bounds.origin.x as i32, bounds.origin.y as i32),
+                })
+            })
+            .collect()
+    }
+
+    fn select_display(&mut self, id: u32) -> Result<(), String> {
+        let active_displays =
+            CGDisplay::active_displays().map_err(|e| format!("Failed to enumerate displays: {:?}", e))?;
+
+        if !active_displays.contains(&id) {
+            return Err(format!("Display {} is not currently attached", id));
+        }
+
+        self.selected_display = CGDisplay::new(id);
+        Ok(())
+    }
+
     fn start_capture(&mut self, _window: Option<&winit::window::Window>) -> Result<(), String> {
         // A real implementation opens a CGDisplayStream on `self.selected_display`,
         // converts each delivered BGRA frame with `pixel_conversion::bgra_to_rgba`,
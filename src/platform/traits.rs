@@ -5,6 +5,19 @@ pub struct DisplayResolution {
     pub height: u32,
 }
 
+/// Static metadata for one attached monitor, as returned by `list_displays`.
+#[derive(Debug, Clone)]
+pub struct DisplayInfo {
+    /// Stable id used to select this display with `select_display`.
+    pub id: u32,
+    pub name: String,
+    pub resolution: DisplayResolution,
+    /// HiDPI scale factor (e.g. 2.0 on a Retina display).
+    pub scale_factor: f64,
+    /// Top-left corner of this display in the OS's virtual desktop space.
+    pub origin: (i32, i32),
+}
+
 /// Platform-specific screen capture backend. Each OS module (`macos`,
 /// `windows`, `linux`) implements this against its own capture API;
 /// `CrossPlatformScreenCapture` picks the right one at construction time and
@@ -14,9 +27,16 @@ pub trait PlatformScreenCapture {
     where
         Self: Sized;
 
-    /// Resolution of the display currently being captured.
+    /// Resolution of the currently selected display (or the primary display,
+    /// before any selection has been made).
     fn get_display_resolution(&mut self) -> Result<DisplayResolution, String>;
 
+    /// Enumerates every attached display.
+    fn list_displays(&mut self) -> Result<Vec<DisplayInfo>, String>;
+
+    /// Switches the capture source to the display with the given id.
+    fn select_display(&mut self, id: u32) -> Result<(), String>;
+
     fn start_capture(&mut self, window: Option<&winit::window::Window>) -> Result<(), String>;
 
     fn get_latest_frame(&mut self) -> Option<Vec<u8>>;
@@ -1,19 +1,29 @@
-use super::traits::{DisplayResolution, PlatformScreenCapture};
+use super::traits::{DisplayInfo, DisplayResolution, PlatformScreenCapture};
 use crate::screen_capture::FrameBuffer;
 use std::sync::Arc;
-use windows::Win32::Foundation::{LPARAM, RECT};
 use windows::Win32::Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFOEXW};
+use windows::Win32::Foundation::{LPARAM, RECT};
 
 /// Windows screen capture backed by the Desktop Duplication API, scoped to
-/// the primary monitor handle.
+/// whichever monitor handle is currently selected.
 pub struct WindowsScreenCapture {
-    selected_monitor: HMONITOR,
+    monitors: Vec<(u32, HMONITOR)>,
+    selected_monitor: Option<HMONITOR>,
     latest_frame: Arc<FrameBuffer>,
 }
 
 impl WindowsScreenCapture {
-    fn primary_monitor() -> Result<HMONITOR, String> {
-        let mut monitors: Vec<HMONITOR> = Vec::new();
+    /// Derives a stable id from the `HMONITOR` handle itself rather than its
+    /// position in an enumeration, so a replug or reorder between a
+    /// `list_displays` call and a later `select_display` call can't silently
+    /// resolve to the wrong monitor. `HMONITOR` handles are stable for the
+    /// lifetime of the monitor's attachment.
+    fn monitor_id(handle: HMONITOR) -> u32 {
+        handle.0 as u32
+    }
+
+    fn enumerate_monitors() -> Vec<(u32, HMONITOR)> {
+        let mut monitors: Vec<(u32, HMONITOR)> = Vec::new();
 
         unsafe extern "system" fn collect(
             monitor: HMONITOR,
@@ -21,8 +31,8 @@ impl WindowsScreenCapture {
             _rect: *mut RECT,
             state: LPARAM,
         ) -> windows::Win32::Foundation::BOOL {
-            let monitors = &mut *(state.0 as *mut Vec<HMONITOR>);
-            monitors.push(monitor);
+            let monitors = &mut *(state.0 as *mut Vec<(u32, HMONITOR)>);
+            monitors.push((WindowsScreenCapture::monitor_id(monitor), monitor));
             true.into()
         }
 
@@ -35,7 +45,7 @@ impl WindowsScreenCapture {
             );
         }
 
-        monitors.first().copied().ok_or_else(|| "No monitors attached".to_string())
+        monitors
     }
 
     fn monitor_info(handle: HMONITOR) -> Result<MONITORINFOEXW, String> {
@@ -50,14 +60,19 @@ impl WindowsScreenCapture {
 
 impl PlatformScreenCapture for WindowsScreenCapture {
     fn new() -> Result<Self, String> {
+        let monitors = Self::enumerate_monitors();
+        let selected_monitor = monitors.first().map(|(_, handle)| *handle);
+
         Ok(Self {
-            selected_monitor: Self::primary_monitor()?,
+            monitors,
+            selected_monitor,
             latest_frame: Arc::new(FrameBuffer::new()),
         })
     }
 
     fn get_display_resolution(&mut self) -> Result<DisplayResolution, String> {
-        let info = Self::monitor_info(self.selected_monitor)?;
+        let handle = self.selected_monitor.ok_or("No monitor selected")?;
+        let info = Self::monitor_info(handle)?;
         let rect = info.monitorInfo.rcMonitor;
 
         Ok(DisplayResolution {
@@ -66,6 +81,44 @@ impl PlatformScreenCapture for WindowsScreenCapture {
         })
     }
 
+    fn list_displays(&mut self) -> Result<Vec<DisplayInfo>, String> {
+        self.monitors = Self::enumerate_monitors();
+
+        self.monitors
+            .iter()
+            .map(|(id, handle)| {
+                let info = Self::monitor_info(*handle)?;
+                let rect = info.monitorInfo.rcMonitor;
+
+                Ok(DisplayInfo {
+                    id: *id,
+                    name: String::from_utf16_lossy(&info.szDevice)
+                        .trim_end_matches('\0')
+                        .to_string(),
+                    resolution: DisplayResolution {
+                        width: (rect.right - rect.left) as u32,
+                        height: (rect.bottom - rect.top) as u32,
+                    },
+                    // A full implementation would read this via GetDpiForMonitor.
+                    scale_factor: 1.0,
+                    origin: (rect.left, rect.top),
+                })
+            })
+            .collect()
+    }
+
+    fn select_display(&mut self, id: u32) -> Result<(), String> {
+        let handle = self
+            .monitors
+            .iter()
+            .find(|(monitor_id, _)| *monitor_id == id)
+            .map(|(_, handle)| *handle)
+            .ok_or_else(|| format!("No monitor with id {}", id))?;
+
+        self.selected_monitor = Some(handle);
+        Ok(())
+    }
+
     fn start_capture(&mut self, _window: Option<&winit::window::Window>) -> Result<(), String> {
         // A real implementation creates an IDXGIOutputDuplication for
         // `self.selected_monitor`, converts each delivered BGRA frame with
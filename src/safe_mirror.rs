@@ -1,5 +1,6 @@
 use crate::{
-    cross_platform_capture::CrossPlatformScreenCapture, gpu_renderer::GpuRenderer,
+    cross_platform_capture::CrossPlatformScreenCapture,
+    gpu_renderer::{GpuRenderer, RedactionRect, RedactionStyle},
     sensitive_data_detector::SensitiveDataDetector,
 };
 use std::sync::Arc;
@@ -19,6 +20,11 @@ pub struct SafeMirror {
 
     /// Cached sensitive matches for persistent redaction
     cached_sensitive_matches: Vec<crate::sensitive_data_detector::SensitiveMatch>,
+
+    /// Style applied to every redaction rect this frame. Cycled at runtime
+    /// via `cycle_redaction_style`, so users can pick whichever reads best
+    /// for their screen.
+    redaction_style: RedactionStyle,
 }
 
 impl SafeMirror {
@@ -66,6 +72,7 @@ impl SafeMirror {
             screen_capture,
             sensitive_detector,
             cached_sensitive_matches: Vec::new(),
+            redaction_style: RedactionStyle::Mosaic,
         }
     }
 
@@ -77,14 +84,14 @@ impl SafeMirror {
 
     /// Updates the screen capture texture with new image data and renders
     pub fn update_and_render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let cpu_frame_started_at = std::time::Instant::now();
+
         // Get latest frame or use test pattern
-        let mut texture_data = self
+        let texture_data = self
             .screen_capture
             .get_latest_frame()
             .unwrap_or_else(|| self.gpu_renderer.create_test_pattern());
 
-        // Remove test redaction since pipeline is confirmed working
-
         // Get display resolution for redaction
         let resolution = self
             .screen_capture
@@ -94,59 +101,92 @@ impl SafeMirror {
                 height: 1080,
             });
 
-        // Detect sensitive data (only on OCR frames) and update cache
+        // Detect sensitive data: the detector itself only runs OCR every 60
+        // frames, but it returns the full tracked-match set (aged/smoothed)
+        // every frame so redaction never flickers between passes. Only log
+        // on the frame OCR actually ran, or this fires 60x/sec instead of once.
         if let Some(ref mut detector) = self.sensitive_detector {
             let new_matches =
                 detector.detect_sensitive_data(&texture_data, resolution.width, resolution.height);
+            self.cached_sensitive_matches = new_matches;
 
-            // Update cache with new detections
-            if !new_matches.is_empty() {
-                self.cached_sensitive_matches = new_matches;
+            if detector.frames_since_last_ocr() == 0 {
                 println!(
                     "🔒 Updated sensitive data cache with {} areas",
                     self.cached_sensitive_matches.len()
                 );
             }
-        }
 
-        // Always apply redaction using cached matches (every frame)
-        if !self.cached_sensitive_matches.is_empty() {
-            if let Some(ref detector) = self.sensitive_detector {
-                detector.apply_redaction(
-                    &mut texture_data,
-                    resolution.width,
-                    resolution.height,
-                    &self.cached_sensitive_matches,
-                );
-            }
+            // OCR only reports a fresh latency on the frame it actually ran;
+            // other frames push `None` so the overlay treats them as a gap
+            // rather than re-plotting a stale sample.
+            let ocr_latency = (detector.frames_since_last_ocr() == 0)
+                .then(|| detector.last_ocr_latency_ms())
+                .flatten();
+            self.gpu_renderer.push_counter_sample("ocr_latency_ms", ocr_latency);
+            self.gpu_renderer
+                .push_counter_sample("frames_since_ocr", Some(detector.frames_since_last_ocr() as f32));
         }
+        self.gpu_renderer
+            .push_counter_sample("redaction_boxes", Some(self.cached_sensitive_matches.len() as f32));
+
+        // Redaction is now a GPU render pass rather than a CPU buffer mutation:
+        // upload the raw frame, then hand the cached matches to the renderer as
+        // normalized rects so the fragment shader can blur/mosaic/fill them.
+        // Mosaic's strength is a block size in normalized UV space; blur's is
+        // a 0..1 knob `GpuRenderer` scales into a pixel sigma. Fill ignores it.
+        let strength = match self.redaction_style {
+            RedactionStyle::Fill => 0.0,
+            RedactionStyle::Mosaic => 0.02,
+            RedactionStyle::Blur => 0.5,
+        };
+        let redaction_rects = self.cached_sensitive_matches.iter().map(|m| RedactionRect {
+            x: m.x as f32 / resolution.width as f32,
+            y: m.y as f32 / resolution.height as f32,
+            width: m.width as f32 / resolution.width as f32,
+            height: m.height as f32 / resolution.height as f32,
+            style: self.redaction_style,
+            strength,
+        }).collect::<Vec<_>>();
+        self.gpu_renderer.set_redaction_rects(&redaction_rects);
 
         // Update GPU texture and render
         self.gpu_renderer.update_texture(&texture_data);
+
+        let cpu_frame_ms = cpu_frame_started_at.elapsed().as_secs_f32() * 1000.0;
+        self.gpu_renderer
+            .push_counter_sample("cpu_frame_ms", Some(cpu_frame_ms));
+
         self.gpu_renderer.render()
     }
 
+    /// Cycles the on-screen diagnostics overlay between hidden, readout, and graph modes.
+    pub fn cycle_overlay_mode(&mut self) {
+        self.gpu_renderer.cycle_overlay_mode();
+    }
+
+    /// Cycles the redaction style applied to every tracked match: Mosaic -> Blur -> Fill -> Mosaic.
+    pub fn cycle_redaction_style(&mut self) {
+        self.redaction_style = match self.redaction_style {
+            RedactionStyle::Mosaic => RedactionStyle::Blur,
+            RedactionStyle::Blur => RedactionStyle::Fill,
+            RedactionStyle::Fill => RedactionStyle::Mosaic,
+        };
+    }
+
     /// Get current window size for resize operations
     pub fn size(&self) -> winit::dpi::PhysicalSize<u32> {
         self.gpu_renderer.size()
     }
 
-    /// Test redaction by blacking out top-left corner
-    fn test_redaction(&self, rgba_buffer: &mut [u8], width: u32, height: u32) {
-        let test_width = 200;
-        let test_height = 100;
-
-        for y in 0..test_height.min(height) {
-            for x in 0..test_width.min(width) {
-                let pixel_index = ((y * width + x) * 4) as usize;
-                if pixel_index + 3 < rgba_buffer.len() {
-                    rgba_buffer[pixel_index] = 255; // R - red for visibility
-                    rgba_buffer[pixel_index + 1] = 0; // G
-                    rgba_buffer[pixel_index + 2] = 0; // B
-                    rgba_buffer[pixel_index + 3] = 255; // A
-                }
-            }
-        }
-        println!("🟥 Applied test redaction (red box) at top-left corner");
+    /// Renders the currently redacted frame offscreen and writes it to `path`
+    /// as a PNG, optionally cropped to a single region, so a user can verify
+    /// what a viewer would actually see.
+    pub fn capture_frame(
+        &mut self,
+        path: &str,
+        crop: Option<crate::gpu_renderer::CropRect>,
+    ) -> Result<(), String> {
+        self.gpu_renderer.capture_to_png(path, crop)
     }
 }
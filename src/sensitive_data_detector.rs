@@ -1,6 +1,7 @@
 use regex::Regex;
 use tesseract::Tesseract;
 use image::{ImageBuffer, Luma};
+use std::collections::HashMap;
 
 /// Detected sensitive information with location
 #[derive(Debug, Clone)]
@@ -14,8 +15,82 @@ pub struct SensitiveMatch {
     pub height: u32,
 }
 
+/// A single word recognized by tesseract, with its bounding box (in source
+/// image pixels) and OCR confidence, used to map regex matches back to real
+/// screen coordinates.
+#[derive(Debug, Clone)]
+struct OcrWord {
+    text: String,
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+    confidence: f32,
+}
+
+/// How many OCR passes worth of frames a tracked match is kept alive for once
+/// it stops being re-confirmed. OCR runs every 60 frames, so 120 covers two
+/// missed passes before a box is dropped.
+const TRACK_TTL_FRAMES: u32 = 120;
+
+/// Minimum IoU between a fresh OCR box and a tracked one to treat them as the
+/// same on-screen region rather than a brand-new detection.
+const TRACK_IOU_THRESHOLD: f32 = 0.5;
+
+/// Weight given to a newly observed box when smoothing tracked coordinates;
+/// keeps redaction boxes from jumping between detection frames.
+const TRACK_SMOOTHING: f32 = 0.5;
+
+/// A `SensitiveMatch` carried across frames between OCR passes, identified by
+/// a stable id so redaction doesn't flicker while tesseract isn't running.
+#[derive(Debug, Clone)]
+struct TrackedMatch {
+    id: u64,
+    ttl: u32,
+    data_type: SensitiveDataType,
+    text: String,
+    confidence: f32,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl TrackedMatch {
+    fn to_sensitive_match(&self) -> SensitiveMatch {
+        SensitiveMatch {
+            data_type: self.data_type.clone(),
+            text: self.text.clone(),
+            confidence: self.confidence,
+            x: self.x.round() as u32,
+            y: self.y.round() as u32,
+            width: self.width.round() as u32,
+            height: self.height.round() as u32,
+        }
+    }
+
+    fn iou(&self, x: f32, y: f32, width: f32, height: f32) -> f32 {
+        let left = self.x.max(x);
+        let top = self.y.max(y);
+        let right = (self.x + self.width).min(x + width);
+        let bottom = (self.y + self.height).min(y + height);
+
+        if right <= left || bottom <= top {
+            return 0.0;
+        }
+
+        let intersection = (right - left) * (bottom - top);
+        let union = self.width * self.height + width * height - intersection;
+        if union <= 0.0 {
+            0.0
+        } else {
+            intersection / union
+        }
+    }
+}
+
 /// Types of sensitive data we can detect
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SensitiveDataType {
     Email,
     CreditCard,
@@ -33,8 +108,29 @@ pub struct SensitiveDataDetector {
     tesseract: Tesseract,
     patterns: Vec<(SensitiveDataType, Regex)>,
     frame_count: u32,
+    /// Matches carried forward between OCR passes, identified and smoothed
+    /// across frames so redaction boxes don't flicker or jump.
+    tracked_matches: Vec<TrackedMatch>,
+    next_match_id: u64,
+    /// How long the most recent OCR pass took, for the diagnostics overlay.
+    /// `None` until the first pass completes.
+    last_ocr_latency_ms: Option<f32>,
+    /// Frames elapsed since OCR last ran, reset to 0 each time it runs.
+    frames_since_last_ocr: u32,
+    /// Matches scoring below this confidence after validation are dropped
+    /// instead of being tracked/redacted.
+    pub confidence_threshold: f32,
+    /// Per-type on/off switch, checked before a match is even validated.
+    pub enabled_types: HashMap<SensitiveDataType, bool>,
 }
 
+/// Default minimum confidence a validated match needs to be kept.
+const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// Multiplier applied to IPv4 matches in private/reserved ranges, since
+/// they're far less likely to be sensitive than a public address.
+const PRIVATE_IP_CONFIDENCE_WEIGHT: f32 = 0.5;
+
 impl SensitiveDataDetector {
     pub fn new() -> Result<Self, String> {
         let tesseract = Tesseract::new(None, Some("eng"))
@@ -42,7 +138,180 @@ impl SensitiveDataDetector {
 
         let patterns = Self::build_patterns();
 
-        Ok(Self { tesseract, patterns, frame_count: 0 })
+        Ok(Self {
+            tesseract,
+            patterns,
+            frame_count: 0,
+            tracked_matches: Vec::new(),
+            next_match_id: 0,
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+            enabled_types: HashMap::new(),
+            last_ocr_latency_ms: None,
+            frames_since_last_ocr: 0,
+        })
+    }
+
+    /// Duration of the most recent OCR pass in milliseconds, for the
+    /// diagnostics overlay. `None` until the first pass has run.
+    pub fn last_ocr_latency_ms(&self) -> Option<f32> {
+        self.last_ocr_latency_ms
+    }
+
+    /// Frames elapsed since OCR last ran.
+    pub fn frames_since_last_ocr(&self) -> u32 {
+        self.frames_since_last_ocr
+    }
+
+    /// Whether `data_type` should be detected at all. Types default to
+    /// enabled; `enabled_types` only needs an entry to turn one off.
+    fn is_type_enabled(&self, data_type: &SensitiveDataType) -> bool {
+        *self.enabled_types.get(data_type).unwrap_or(&true)
+    }
+
+    /// Runs checksum/structural validation for types that support it and
+    /// returns the confidence the match should be kept with. Types without a
+    /// real structural check (email, phone, API key, URL) keep their OCR
+    /// confidence as-is.
+    fn validate_confidence(data_type: &SensitiveDataType, text: &str, ocr_confidence: f32) -> f32 {
+        match data_type {
+            SensitiveDataType::CreditCard => {
+                if Self::is_valid_credit_card(text) {
+                    ocr_confidence.max(0.9)
+                } else {
+                    0.0
+                }
+            }
+            SensitiveDataType::SocialSecurityNumber => {
+                if Self::is_valid_ssn(text) {
+                    ocr_confidence.max(0.9)
+                } else {
+                    0.0
+                }
+            }
+            SensitiveDataType::IpAddress => {
+                if !Self::is_valid_ipv4(text) {
+                    0.0
+                } else if Self::is_private_ipv4(text) {
+                    ocr_confidence * PRIVATE_IP_CONFIDENCE_WEIGHT
+                } else {
+                    ocr_confidence
+                }
+            }
+            SensitiveDataType::BankAccount => {
+                if Self::is_valid_bank_account(text) {
+                    ocr_confidence
+                } else {
+                    0.0
+                }
+            }
+            _ => ocr_confidence,
+        }
+    }
+
+    /// Luhn checksum plus a known-issuer prefix/length check, to cut down on
+    /// the flood of false positives `\b(?:\d{4}[-\s]?){3}\d{1,7}\b` produces
+    /// against any long number on screen.
+    fn is_valid_credit_card(text: &str) -> bool {
+        let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+
+        if !Self::luhn_checksum_valid(&digits) {
+            return false;
+        }
+
+        let len = digits.len();
+        let starts_with = |prefix: &str| digits.starts_with(prefix);
+
+        let is_visa = starts_with("4") && (len == 13 || len == 16 || len == 19);
+        let is_amex = (starts_with("34") || starts_with("37")) && len == 15;
+        let is_mastercard = len == 16
+            && digits[..2]
+                .parse::<u32>()
+                .map(|p| (51..=55).contains(&p))
+                .unwrap_or(false)
+            || len == 16
+                && digits[..4]
+                    .parse::<u32>()
+                    .map(|p| (2221..=2720).contains(&p))
+                    .unwrap_or(false);
+
+        is_visa || is_amex || is_mastercard
+    }
+
+    fn luhn_checksum_valid(digits: &str) -> bool {
+        if digits.len() < 12 {
+            return false;
+        }
+
+        let sum: u32 = digits
+            .chars()
+            .rev()
+            .enumerate()
+            .map(|(i, c)| {
+                let digit = c.to_digit(10).unwrap_or(0);
+                if i % 2 == 1 {
+                    let doubled = digit * 2;
+                    if doubled > 9 {
+                        doubled - 9
+                    } else {
+                        doubled
+                    }
+                } else {
+                    digit
+                }
+            })
+            .sum();
+
+        sum % 10 == 0
+    }
+
+    /// Rejects SSNs with an invalid area, group, or serial number, per the
+    /// SSA's allocation rules (area 000/666/900-999, group 00, serial 0000
+    /// are never issued).
+    fn is_valid_ssn(text: &str) -> bool {
+        let digits: Vec<u32> = text.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 9 {
+            return false;
+        }
+
+        let area = digits[0] * 100 + digits[1] * 10 + digits[2];
+        let group = digits[3] * 10 + digits[4];
+        let serial = digits[5] * 1000 + digits[6] * 100 + digits[7] * 10 + digits[8];
+
+        area != 0 && area != 666 && area < 900 && group != 0 && serial != 0
+    }
+
+    fn is_valid_ipv4(text: &str) -> bool {
+        text.split('.')
+            .map(|octet| octet.parse::<u16>())
+            .all(|octet| matches!(octet, Ok(value) if value <= 255))
+    }
+
+    /// RFC 1918 private ranges plus loopback, which are common on screen but
+    /// rarely sensitive the way a public IP leaking an internal host would be.
+    fn is_private_ipv4(text: &str) -> bool {
+        let octets: Vec<u16> = text.split('.').filter_map(|o| o.parse().ok()).collect();
+        let [a, b, ..] = octets[..] else { return false };
+
+        a == 10 || a == 127 || (a == 172 && (16..=31).contains(&b)) || (a == 192 && b == 168)
+    }
+
+    /// Rejects the placeholder-shaped runs that make `\b\d{8,17}\b` flag
+    /// nearly every long number on screen: all-same-digit runs (padding
+    /// zeros, repeated-digit test values) and strictly sequential runs
+    /// (timestamps, line numbers, auto-incrementing IDs). Neither carries a
+    /// checksum to validate against, so this is the best signal available.
+    fn is_valid_bank_account(text: &str) -> bool {
+        let digits: String = text.chars().filter(|c| c.is_ascii_digit()).collect();
+        if digits.len() < 8 || digits.len() > 17 {
+            return false;
+        }
+
+        let bytes = digits.as_bytes();
+        let all_same = bytes.iter().all(|&b| b == bytes[0]);
+        let ascending = bytes.windows(2).all(|w| w[1] == w[0] + 1);
+        let descending = bytes.windows(2).all(|w| w[0] == w[1] + 1);
+
+        !all_same && !ascending && !descending
     }
 
     /// Build regex patterns for detecting sensitive data
@@ -92,83 +361,218 @@ impl SensitiveDataDetector {
         patterns
     }
 
-    /// Detect sensitive data in RGBA image buffer  
+    /// Detect sensitive data in RGBA image buffer. Returns the full set of
+    /// currently tracked matches every frame (not just the ones freshly
+    /// confirmed by OCR this frame), so callers always have real, stable
+    /// bounding boxes to redact even between OCR passes.
     pub fn detect_sensitive_data(&mut self, rgba_buffer: &[u8], width: u32, height: u32) -> Vec<SensitiveMatch> {
-        let mut matches = Vec::new();
-        
         self.frame_count += 1;
-        
+
+        // Age out tracked boxes every frame so stale detections eventually
+        // stop being redacted even if OCR never runs again.
+        for tracked in &mut self.tracked_matches {
+            tracked.ttl = tracked.ttl.saturating_sub(1);
+        }
+        self.tracked_matches.retain(|tracked| tracked.ttl > 0);
+
         // Only run OCR every 60 frames (roughly once per second) to avoid performance issues
-        if self.frame_count % 60 != 0 {
-            return matches;
+        if self.frame_count % 60 == 0 {
+            println!("🔍 Running OCR analysis on frame {}", self.frame_count);
+
+            let started_at = std::time::Instant::now();
+            match self.run_ocr_pass(rgba_buffer, width, height) {
+                Ok(words) => self.merge_ocr_words(&words),
+                Err(e) => eprintln!("OCR pass failed: {}", e),
+            }
+            self.last_ocr_latency_ms = Some(started_at.elapsed().as_secs_f32() * 1000.0);
+            self.frames_since_last_ocr = 0;
+        } else {
+            self.frames_since_last_ocr += 1;
         }
 
-        println!("🔍 Running OCR analysis on frame {}", self.frame_count);
+        self.tracked_matches.iter().map(TrackedMatch::to_sensitive_match).collect()
+    }
+
+    /// Forces an OCR pass immediately, bypassing the normal "every 60
+    /// frames" throttle. Used by the headless driver, which processes one
+    /// static frame at a time and has no frame counter to wait on.
+    pub fn detect_sensitive_data_now(&mut self, rgba_buffer: &[u8], width: u32, height: u32) -> Vec<SensitiveMatch> {
+        let started_at = std::time::Instant::now();
+        match self.run_ocr_pass(rgba_buffer, width, height) {
+            Ok(words) => self.merge_ocr_words(&words),
+            Err(e) => eprintln!("OCR pass failed: {}", e),
+        }
+        self.last_ocr_latency_ms = Some(started_at.elapsed().as_secs_f32() * 1000.0);
+        self.frames_since_last_ocr = 0;
 
+        self.tracked_matches.iter().map(TrackedMatch::to_sensitive_match).collect()
+    }
+
+    /// Runs tesseract over the frame and returns per-word bounding boxes and
+    /// confidences via the TSV output, instead of the flat `get_text()` string.
+    fn run_ocr_pass(&self, rgba_buffer: &[u8], width: u32, height: u32) -> Result<Vec<OcrWord>, String> {
         // Convert RGBA to grayscale for OCR
         let grayscale = self.rgba_to_grayscale(rgba_buffer, width, height);
 
         // Save grayscale image temporarily for tesseract
         let temp_file = "cloak_share_ocr.png";
-        match self.save_grayscale_as_png(&grayscale, width, height, temp_file) {
-            Ok(_) => println!("📷 Successfully saved frame to {} ({}x{})", temp_file, width, height),
-            Err(e) => {
-                eprintln!("Failed to save image for OCR: {}", e);
-                return matches;
+        self.save_grayscale_as_png(&grayscale, width, height, temp_file)?;
+        println!("📷 Successfully saved frame to {} ({}x{})", temp_file, width, height);
+
+        let tess = Tesseract::new(None, Some("eng"))
+            .map_err(|e| format!("Failed to initialize tesseract: {}", e))?;
+        let mut tess_with_image = tess
+            .set_image(temp_file)
+            .map_err(|e| format!("Failed to set image: {}", e))?;
+        let tsv = tess_with_image
+            .get_tsv_text(0)
+            .map_err(|e| format!("Failed to get TSV text: {}", e));
+
+        let _ = std::fs::remove_file(temp_file);
+
+        Self::parse_tsv_words(&tsv?)
+    }
+
+    /// Parses tesseract's TSV output (level, page_num, block_num, par_num,
+    /// line_num, word_num, left, top, width, height, conf, text) into words,
+    /// keeping only level-5 (word) rows with actual text.
+    fn parse_tsv_words(tsv: &str) -> Result<Vec<OcrWord>, String> {
+        let mut words = Vec::new();
+
+        for line in tsv.lines().skip(1) {
+            let columns: Vec<&str> = line.split('\t').collect();
+            if columns.len() < 12 {
+                continue;
+            }
+            if columns[0] != "5" {
+                continue; // Only word-level rows carry recognized text
             }
-        }
 
-        // Extract text using tesseract
-        let text = match Tesseract::new(None, Some("eng")) {
-            Ok(tess) => {
-                match tess.set_image(temp_file) {
-                    Ok(mut tess_with_image) => {
-                        match tess_with_image.get_text() {
-                            Ok(extracted_text) => extracted_text,
-                            Err(e) => {
-                                eprintln!("Failed to get text: {}", e);
-                                return matches;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to set image: {}", e);
-                        return matches;
-                    }
-                }
+            let text = columns[11].trim();
+            if text.is_empty() {
+                continue;
             }
-            Err(e) => {
-                eprintln!("Failed to initialize tesseract: {}", e);
-                return matches;
+
+            let left: u32 = columns[6].parse().unwrap_or(0);
+            let top: u32 = columns[7].parse().unwrap_or(0);
+            let width: u32 = columns[8].parse().unwrap_or(0);
+            let height: u32 = columns[9].parse().unwrap_or(0);
+            let confidence: f32 = columns[10].parse().unwrap_or(-1.0);
+
+            if confidence < 0.0 {
+                continue; // Tesseract uses -1 for non-text container rows
             }
-        };
 
-        // Clean up temp file
-        let _ = std::fs::remove_file(temp_file);
+            words.push(OcrWord {
+                text: text.to_string(),
+                left,
+                top,
+                width,
+                height,
+                confidence: confidence / 100.0,
+            });
+        }
+
+        Ok(words)
+    }
+
+    /// Reconstructs a single string from the OCR words (joined by spaces, so
+    /// regexes like email/phone still match across word boundaries), runs the
+    /// sensitive-data patterns over it, and maps each match back to the union
+    /// of the words it came from. Matches are then merged into `tracked_matches`
+    /// by IoU so an already-tracked box keeps its identity and gets smoothed
+    /// rather than replaced outright.
+    fn merge_ocr_words(&mut self, words: &[OcrWord]) {
+        let mut reconstructed = String::new();
+        let mut word_spans = Vec::with_capacity(words.len());
+
+        for word in words {
+            let start = reconstructed.len();
+            reconstructed.push_str(&word.text);
+            word_spans.push((start, reconstructed.len()));
+            reconstructed.push(' ');
+        }
+
+        let mut fresh_matches = Vec::new();
 
-        // Apply pattern matching to extracted text
         for (data_type, pattern) in &self.patterns {
-            for regex_match in pattern.find_iter(&text) {
-                let sensitive_text = regex_match.as_str();
-                
-                matches.push(SensitiveMatch {
-                    data_type: data_type.clone(),
-                    text: sensitive_text.to_string(),
-                    confidence: 0.8,
-                    x: 0, // TODO: Get actual coordinates from tesseract bounding boxes
-                    y: 0,
-                    width: 0,
-                    height: 0,
-                });
-
-                println!(
-                    "🔍 SENSITIVE DATA DETECTED: {:?} - '{}'",
-                    data_type, sensitive_text
-                );
+            if !self.is_type_enabled(data_type) {
+                continue;
+            }
+
+            for regex_match in pattern.find_iter(&reconstructed) {
+                let (start, end) = (regex_match.start(), regex_match.end());
+
+                let contributing: Vec<&OcrWord> = words
+                    .iter()
+                    .zip(&word_spans)
+                    .filter(|(_, (word_start, word_end))| *word_start < end && *word_end > start)
+                    .map(|(word, _)| word)
+                    .collect();
+
+                if contributing.is_empty() {
+                    continue;
+                }
+
+                let left = contributing.iter().map(|w| w.left).min().unwrap();
+                let top = contributing.iter().map(|w| w.top).min().unwrap();
+                let right = contributing.iter().map(|w| w.left + w.width).max().unwrap();
+                let bottom = contributing.iter().map(|w| w.top + w.height).max().unwrap();
+                let ocr_confidence =
+                    contributing.iter().map(|w| w.confidence).sum::<f32>() / contributing.len() as f32;
+
+                let text = regex_match.as_str();
+                let confidence = Self::validate_confidence(data_type, text, ocr_confidence);
+                if confidence < self.confidence_threshold {
+                    continue;
+                }
+
+                println!("🔍 SENSITIVE DATA DETECTED: {:?} - '{}'", data_type, text);
+
+                fresh_matches.push((
+                    *data_type,
+                    text.to_string(),
+                    confidence,
+                    left as f32,
+                    top as f32,
+                    (right - left) as f32,
+                    (bottom - top) as f32,
+                ));
             }
         }
 
-        matches
+        for (data_type, text, confidence, x, y, width, height) in fresh_matches {
+            let existing = self.tracked_matches.iter_mut().find(|tracked| {
+                tracked.data_type == data_type && tracked.iou(x, y, width, height) > TRACK_IOU_THRESHOLD
+            });
+
+            match existing {
+                Some(tracked) => {
+                    tracked.x = tracked.x + (x - tracked.x) * TRACK_SMOOTHING;
+                    tracked.y = tracked.y + (y - tracked.y) * TRACK_SMOOTHING;
+                    tracked.width = tracked.width + (width - tracked.width) * TRACK_SMOOTHING;
+                    tracked.height = tracked.height + (height - tracked.height) * TRACK_SMOOTHING;
+                    tracked.text = text;
+                    tracked.confidence = confidence;
+                    tracked.ttl = TRACK_TTL_FRAMES;
+                }
+                None => {
+                    let id = self.next_match_id;
+                    self.next_match_id += 1;
+                    self.tracked_matches.push(TrackedMatch {
+                        id,
+                        ttl: TRACK_TTL_FRAMES,
+                        data_type,
+                        text,
+                        confidence,
+                        x,
+                        y,
+                        width,
+                        height,
+                    });
+                }
+            }
+        }
     }
 
     /// Convert RGBA buffer to grayscale for OCR